@@ -2,16 +2,19 @@ use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
 use bevy::math::Vec3Swizzles;
 use bevy::prelude::*;
 use bevy::sprite::MaterialMesh2dBundle;
-use bevy::window::Window;
+use bevy::window::{PrimaryWindow, Window};
 use bevy_inspector_egui::inspector_options::std_options::NumberDisplay;
 use bevy_inspector_egui::InspectorOptions;
 use bevy_inspector_egui::prelude::ReflectInspectorOptions;
 use bevy_inspector_egui::quick::{ResourceInspectorPlugin, WorldInspectorPlugin};
 use bevy_window_title_diagnostics::WindowTitleLoggerDiagnosticsPlugin;
 use rand::prelude::*;
+use std::collections::HashMap;
 
 const PARTICLE_SIZE: f32 = 0.1;
 const MASS: f32 = 1.;
+const PALETTE_BINS: usize = 32;
+const SPEED_SCALE: f32 = 10.;
 const WINDOW_WIDTH: f32 = 1920.;
 const WINDOW_HEIGHT: f32 = 1080.;
 
@@ -19,7 +22,10 @@ fn main() {
     App::new()
         .insert_resource(ClearColor(Color::rgb(0.2, 0.2, 0.2)))
         .insert_resource(SimConfig::default())
+        .init_resource::<SpatialHash>()
         .register_type::<SimConfig>()
+        .register_type::<Boundary>()
+        .register_type::<Visualization>()
         .add_plugins(DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
                 resolution: (WINDOW_WIDTH, WINDOW_HEIGHT).into(),
@@ -31,18 +37,47 @@ fn main() {
         .add_plugins(ResourceInspectorPlugin::<SimConfig>::default())
         .add_plugins(WindowTitleLoggerDiagnosticsPlugin { ..Default::default() })
         .add_plugins(FrameTimeDiagnosticsPlugin::default())
-        .add_systems(Startup, (spawn_camera, spawn_random_scene))
-        .add_systems(Update, (
+        .add_systems(Startup, (spawn_camera, spawn_random_scene, setup_color_palette))
+        .add_systems(Update, (sync_fixed_timestep, update_particle_colors, draw_gizmos))
+        .add_systems(FixedUpdate, (
             // apply_gravity,
+            build_spatial_hash,
+            apply_interaction_force,
             apply_pressure_force,
+            apply_viscosity_force,
             update_density,
+            compute_vorticity,
+            apply_vorticity_force,
             update_position,
             resolve_collision,
-            draw_gizmos
-        ))
+        ).chain())
         .run();
 }
 
+/// How a single domain edge treats particles that cross it.
+#[derive(Reflect, Default, Clone, Copy, PartialEq, Eq, Debug)]
+enum Boundary {
+    /// Bounce particles back into the domain with damping (the sealed-box default).
+    #[default]
+    Reflect,
+    /// Let fluid drain out: particles leaving this edge are despawned.
+    Open,
+    /// Wrap particles to the opposite edge, preserving velocity (toroidal flow).
+    Periodic,
+}
+
+/// Which field the particle coloring visualizes each frame.
+#[derive(Reflect, Default, Clone, Copy, PartialEq, Eq, Debug)]
+enum Visualization {
+    /// Map `velocity.length()` through a blue→white→red gradient.
+    #[default]
+    Speed,
+    /// Map `Density` relative to `target_density` (compressed warm, rarefied cool).
+    Density,
+    /// Color by `convert_density_to_pressure(...)`.
+    Pressure,
+}
+
 #[derive(Reflect, Resource, InspectorOptions)]
 #[reflect(Resource, InspectorOptions)]
 struct SimConfig {
@@ -55,6 +90,21 @@ struct SimConfig {
     particles_spacing: f32,
     target_density: f32,
     pressure_multiplier: f32,
+    #[inspector(min = 1, max = 16, display = NumberDisplay::Slider)]
+    substeps: u32,
+    #[inspector(min = 0., max = 20., speed = 0.1, display = NumberDisplay::Slider)]
+    interaction_radius: f32,
+    #[inspector(min = 0., max = 200., speed = 1., display = NumberDisplay::Slider)]
+    interaction_strength: f32,
+    #[inspector(min = 0., max = 10., speed = 0.05, display = NumberDisplay::Slider)]
+    viscosity_strength: f32,
+    #[inspector(min = 0., max = 10., speed = 0.05, display = NumberDisplay::Slider)]
+    vorticity_epsilon: f32,
+    boundary_left: Boundary,
+    boundary_right: Boundary,
+    boundary_top: Boundary,
+    boundary_bottom: Boundary,
+    visualization: Visualization,
 }
 
 impl Default for SimConfig {
@@ -68,7 +118,90 @@ impl Default for SimConfig {
             particles_spacing: 2. * PARTICLE_SIZE + 0.02,
             target_density: 2.75,
             pressure_multiplier: 0.5,
+            substeps: 3,
+            interaction_radius: 3.5,
+            interaction_strength: 40.,
+            viscosity_strength: 0.5,
+            vorticity_epsilon: 0.3,
+            boundary_left: Boundary::Reflect,
+            boundary_right: Boundary::Reflect,
+            boundary_top: Boundary::Reflect,
+            boundary_bottom: Boundary::Reflect,
+            visualization: Visualization::Speed,
+        }
+    }
+}
+
+/// Uniform spatial hash rebuilt every fixed physics step so the density and pressure passes
+/// only have to visit nearby particles instead of the whole set. The cell size
+/// matches `SimConfig::smoothing_radius`, so every particle within the kernel's
+/// support lives in the particle's own cell or one of the eight around it.
+#[derive(Resource, Default)]
+struct SpatialHash {
+    cell_size: f32,
+    positions: Vec<Vec2>,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+    /// Full domain size, used to wrap neighbour gathering across periodic edges.
+    bounds_size: Vec2,
+    periodic: BVec2,
+    min_cell: (i32, i32),
+    cell_count: (i32, i32),
+}
+
+impl SpatialHash {
+    fn cell_of(&self, position: Vec2) -> (i32, i32) {
+        (
+            (position.x / self.cell_size).floor() as i32,
+            (position.y / self.cell_size).floor() as i32,
+        )
+    }
+
+    /// Wrap a cell coordinate back into the occupied range along any periodic
+    /// axis so a particle near one edge also sees the cells on the opposite one.
+    fn wrap_cell(&self, (cx, cy): (i32, i32)) -> (i32, i32) {
+        let wrap = |c: i32, min: i32, count: i32| {
+            if count <= 0 {
+                c
+            } else {
+                min + (c - min).rem_euclid(count)
+            }
+        };
+        (
+            if self.periodic.x { wrap(cx, self.min_cell.0, self.cell_count.0) } else { cx },
+            if self.periodic.y { wrap(cy, self.min_cell.1, self.cell_count.1) } else { cy },
+        )
+    }
+
+    /// Indices of every particle in the cell containing `position` and its eight
+    /// neighbours — the candidates whose kernel can reach `position`.
+    fn neighbours(&self, position: Vec2) -> impl Iterator<Item = usize> + '_ {
+        let (cx, cy) = self.cell_of(position);
+        (-1..=1)
+            .flat_map(move |dx| (-1..=1).map(move |dy| self.wrap_cell((cx + dx, cy + dy))))
+            .filter_map(move |cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+    }
+
+    /// Vector from `from` to `to`, taking the shortest path across periodic edges
+    /// (minimum image) so contributions wrap correctly around the seam.
+    fn to_neighbour(&self, from: Vec2, to: Vec2) -> Vec2 {
+        let mut delta = to - from;
+        if self.periodic.x {
+            if delta.x > self.bounds_size.x * 0.5 {
+                delta.x -= self.bounds_size.x;
+            } else if delta.x < -self.bounds_size.x * 0.5 {
+                delta.x += self.bounds_size.x;
+            }
+        }
+        if self.periodic.y {
+            if delta.y > self.bounds_size.y * 0.5 {
+                delta.y -= self.bounds_size.y;
+            } else if delta.y < -self.bounds_size.y * 0.5 {
+                delta.y += self.bounds_size.y;
+            }
         }
+        delta
     }
 }
 
@@ -78,9 +211,19 @@ struct Velocity(Vec2);
 #[derive(Component, Deref, DerefMut)]
 struct Density(f32);
 
+#[derive(Component, Deref, DerefMut)]
+struct Vorticity(f32);
+
 #[derive(Component)]
 struct WaterAtom;
 
+/// Pre-allocated gradient so coloring can swap a particle's material handle to the
+/// nearest bin instead of spawning a fresh `ColorMaterial` every frame.
+#[derive(Resource)]
+struct ColorPalette {
+    bins: Vec<Handle<ColorMaterial>>,
+}
+
 fn spawn_camera(mut commands: Commands) {
     commands.spawn(Camera2dBundle {
         projection: OrthographicProjection {
@@ -115,6 +258,7 @@ fn spawn_ordered_scene(
             },
             Velocity(Vec2::ZERO),
             Density(0.),
+            Vorticity(0.),
             WaterAtom,
         ));
     }
@@ -143,6 +287,7 @@ fn spawn_random_scene(
             },
             Velocity(Vec2::ZERO),
             Density(1.),
+            Vorticity(0.),
             WaterAtom,
         ));
     }
@@ -166,10 +311,83 @@ fn smoothing_kernel_derivative(radius: f32, dst: f32) -> f32 {
     scale * dst * f * f
 }
 
+fn viscosity_kernel(radius: f32, dst: f32) -> f32 {
+    if dst >= radius {
+        return 0.;
+    }
+
+    let scale = 45. / (std::f32::consts::PI * radius.powf(6.));
+
+    scale * (radius - dst)
+}
+
 fn convert_density_to_pressure(density: f32, target_density: f32, pressure_multiplier: f32) -> f32 {
     (density - target_density) * pressure_multiplier
 }
 
+/// Keep the `FixedUpdate` rate at `60 * substeps` Hz so the physics cycle runs
+/// `substeps` times per 1/60 s tick, each integrating `dt = 1/(60 * substeps)`.
+/// Smaller sub-steps keep the stiff pressure term stable regardless of the
+/// rendering frame rate.
+fn sync_fixed_timestep(
+    sim_config: Res<SimConfig>,
+    mut fixed_time: ResMut<Time<Fixed>>,
+) {
+    if sim_config.is_changed() {
+        fixed_time.set_timestep_hz(60. * sim_config.substeps.max(1) as f64);
+    }
+}
+
+/// Blue → white → red ramp used for every visualization mode.
+fn gradient_color(t: f32) -> Color {
+    let t = t.clamp(0., 1.);
+    if t < 0.5 {
+        let k = t / 0.5;
+        Color::rgb(k, k, 1.)
+    } else {
+        let k = (t - 0.5) / 0.5;
+        Color::rgb(1., 1. - k, 1. - k)
+    }
+}
+
+fn setup_color_palette(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let bins = (0..PALETTE_BINS)
+        .map(|i| {
+            let t = i as f32 / (PALETTE_BINS - 1) as f32;
+            materials.add(ColorMaterial::from(gradient_color(t)))
+        })
+        .collect();
+
+    commands.insert_resource(ColorPalette { bins });
+}
+
+/// Recolor every particle from the selected field by swapping its material handle
+/// to the nearest palette bin.
+fn update_particle_colors(
+    sim_config: Res<SimConfig>,
+    palette: Res<ColorPalette>,
+    mut query: Query<(&mut Handle<ColorMaterial>, &Velocity, &Density), With<WaterAtom>>,
+) {
+    for (mut material, velocity, density) in query.iter_mut() {
+        let t = match sim_config.visualization {
+            Visualization::Speed => velocity.length() / SPEED_SCALE,
+            Visualization::Density => 0.5 + 0.5 * (**density / sim_config.target_density - 1.),
+            Visualization::Pressure => {
+                0.5 + 0.5 * convert_density_to_pressure(**density, sim_config.target_density, sim_config.pressure_multiplier)
+            }
+        };
+
+        let index = (t.clamp(0., 1.) * (PALETTE_BINS - 1) as f32).round() as usize;
+        let bin = palette.bins[index].clone();
+        if *material != bin {
+            *material = bin;
+        }
+    }
+}
+
 fn draw_gizmos(
     mut gizmos: Gizmos,
     sim_config: Res<SimConfig>,
@@ -182,40 +400,69 @@ fn draw_gizmos(
     );
 }
 
+fn build_spatial_hash(
+    sim_config: Res<SimConfig>,
+    mut spatial_hash: ResMut<SpatialHash>,
+    query: Query<&Transform, With<WaterAtom>>,
+) {
+    spatial_hash.cell_size = sim_config.smoothing_radius;
+    spatial_hash.bounds_size = sim_config.bounds_size;
+    spatial_hash.periodic = BVec2::new(
+        sim_config.boundary_left == Boundary::Periodic || sim_config.boundary_right == Boundary::Periodic,
+        sim_config.boundary_top == Boundary::Periodic || sim_config.boundary_bottom == Boundary::Periodic,
+    );
+    let half = sim_config.bounds_size * 0.5;
+    let min_x = (-half.x / sim_config.smoothing_radius).floor() as i32;
+    let min_y = (-half.y / sim_config.smoothing_radius).floor() as i32;
+    let max_x = (half.x / sim_config.smoothing_radius).floor() as i32;
+    let max_y = (half.y / sim_config.smoothing_radius).floor() as i32;
+    spatial_hash.min_cell = (min_x, min_y);
+    spatial_hash.cell_count = (max_x - min_x + 1, max_y - min_y + 1);
+    spatial_hash.positions = query.iter().map(|transform| transform.translation.xy()).collect();
+
+    spatial_hash.cells.values_mut().for_each(Vec::clear);
+    for i in 0..spatial_hash.positions.len() {
+        let cell = spatial_hash.cell_of(spatial_hash.positions[i]);
+        spatial_hash.cells.entry(cell).or_default().push(i);
+    }
+    spatial_hash.cells.retain(|_, indices| !indices.is_empty());
+}
+
 fn update_density(
     sim_config: Res<SimConfig>,
-    mut query: Query<(&mut Density, &Transform)>,
+    spatial_hash: Res<SpatialHash>,
+    mut query: Query<&mut Density, With<WaterAtom>>,
 ) {
-    let mut densities = Vec::with_capacity(sim_config.particles_num as usize);
-    let particles_positions = query.iter().map(|(_, transform)| transform.translation.xy()).collect::<Vec<_>>();
+    let positions = &spatial_hash.positions;
+    let mut densities = Vec::with_capacity(positions.len());
 
     // calc destiny
-    for particles_position in particles_positions {
+    for &particles_position in positions {
         let mut density = 0.;
 
-        for (_, transform) in query.iter_mut() {
-            let position = transform.translation.xy();
-            let dst = (position - particles_position).length();
+        for j in spatial_hash.neighbours(particles_position) {
+            let dst = spatial_hash.to_neighbour(particles_position, positions[j]).length();
             let influence = smoothing_kernel(sim_config.smoothing_radius, dst);
             density += MASS * influence;
         }
         densities.push(density);
     }
 
-    for (i, (mut density, _)) in query.iter_mut().enumerate() {
+    for (i, mut density) in query.iter_mut().enumerate() {
         **density = densities[i];
     }
 }
 
-fn calculate_pressure_force(sample_point: Vec2, positions: &[Vec2], densities: &[f32], sim_config: &SimConfig) -> Vec2 {
+fn calculate_pressure_force(sample_point: Vec2, densities: &[f32], spatial_hash: &SpatialHash, sim_config: &SimConfig) -> Vec2 {
     let mut pressure_force = Vec2::ZERO;
-    for i in 0..positions.len() {
-        let dst = (positions[i] - sample_point).length();
+    for i in spatial_hash.neighbours(sample_point) {
+        let offset = spatial_hash.to_neighbour(sample_point, spatial_hash.positions[i]);
+        let dst = offset.length();
         let dir = if dst <= 0.0001 {
             // todo: change it to random direction
             Vec2::X
         } else {
-            (positions[i] - sample_point) / dst
+            offset / dst
         };
         let slope = smoothing_kernel_derivative(sim_config.smoothing_radius, dst);
         let pressure = -convert_density_to_pressure(densities[i], sim_config.target_density, sim_config.pressure_multiplier);
@@ -235,23 +482,58 @@ fn apply_gravity(
     }
 }
 
+fn apply_interaction_force(
+    time: Res<Time>,
+    sim_config: Res<SimConfig>,
+    mouse_button: Res<Input<MouseButton>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    mut query: Query<(&mut Velocity, &Transform), With<WaterAtom>>,
+) {
+    let pull = mouse_button.pressed(MouseButton::Left);
+    let push = mouse_button.pressed(MouseButton::Right);
+    if !pull && !push {
+        return;
+    }
+
+    let Ok(window) = window_query.get_single() else { return; };
+    let Ok((camera, camera_transform)) = camera_query.get_single() else { return; };
+    let Some(cursor) = window.cursor_position() else { return; };
+    let Some(cursor_world) = camera.viewport_to_world_2d(camera_transform, cursor) else { return; };
+
+    // Left button pulls particles towards the cursor, right button pushes them away.
+    let sign = if pull { 1. } else { -1. };
+
+    for (mut velocity, transform) in query.iter_mut() {
+        let offset = cursor_world - transform.translation.xy();
+        let dst = offset.length();
+        if dst <= 0.0001 || dst >= sim_config.interaction_radius {
+            continue;
+        }
+
+        // Falls off smoothly to zero at the radius edge.
+        let falloff = 1. - dst / sim_config.interaction_radius;
+        let acceleration = (offset / dst) * sign * sim_config.interaction_strength * falloff;
+        **velocity += acceleration * time.delta_seconds();
+    }
+}
+
 fn apply_pressure_force(
     time: Res<Time>,
     sim_config: Res<SimConfig>,
+    spatial_hash: Res<SpatialHash>,
     mut query: Query<(&mut Velocity, &Transform, &Density)>,
 ) {
-    let mut positions = Vec::with_capacity(sim_config.particles_num as usize);
     let mut densities = Vec::with_capacity(sim_config.particles_num as usize);
-    for (_, transform, density) in query.iter() {
-        positions.push(transform.translation.xy());
+    for (_, _, density) in query.iter() {
         densities.push(**density);
     }
 
     for (mut velocity, transform, density) in query.iter_mut() {
         let pressure_force = calculate_pressure_force(
             transform.translation.xy(),
-            &positions,
             &densities,
+            &spatial_hash,
             &sim_config,
         );
         let pressure_acceleration = pressure_force / **density;
@@ -259,6 +541,97 @@ fn apply_pressure_force(
     }
 }
 
+fn apply_viscosity_force(
+    time: Res<Time>,
+    sim_config: Res<SimConfig>,
+    spatial_hash: Res<SpatialHash>,
+    mut query: Query<(&mut Velocity, &Transform), With<WaterAtom>>,
+) {
+    let velocities = query.iter().map(|(velocity, _)| velocity.0).collect::<Vec<_>>();
+
+    for (mut velocity, transform) in query.iter_mut() {
+        let sample_point = transform.translation.xy();
+        let mut viscosity_force = Vec2::ZERO;
+
+        for j in spatial_hash.neighbours(sample_point) {
+            let dst = spatial_hash.to_neighbour(sample_point, spatial_hash.positions[j]).length();
+            let influence = viscosity_kernel(sim_config.smoothing_radius, dst);
+            viscosity_force += (velocities[j] - velocity.0) * influence;
+        }
+
+        **velocity += viscosity_force * sim_config.viscosity_strength * time.delta_seconds();
+    }
+}
+
+/// First vorticity pass: compute each particle's scalar curl `ω_i` so all curls
+/// are available before the confinement force is applied in the second pass.
+fn compute_vorticity(
+    sim_config: Res<SimConfig>,
+    spatial_hash: Res<SpatialHash>,
+    mut query: Query<(&mut Vorticity, &Transform, &Velocity, &Density)>,
+) {
+    let velocities = query.iter().map(|(_, _, velocity, _)| velocity.0).collect::<Vec<_>>();
+    let densities = query.iter().map(|(_, _, _, density)| **density).collect::<Vec<_>>();
+
+    for (i, (mut vorticity, transform, velocity, _)) in query.iter_mut().enumerate() {
+        let sample_point = transform.translation.xy();
+        let mut curl = 0.;
+
+        for j in spatial_hash.neighbours(sample_point) {
+            if j == i {
+                continue;
+            }
+            let offset = spatial_hash.to_neighbour(sample_point, spatial_hash.positions[j]);
+            let dst = offset.length();
+            if dst <= 0.0001 {
+                continue;
+            }
+            let gradient = smoothing_kernel_derivative(sim_config.smoothing_radius, dst) * (offset / dst);
+            let relative = velocities[j] - velocity.0;
+            // 2D cross product `relative × ∇W` collapses to a scalar.
+            curl += (MASS / densities[j]) * (relative.x * gradient.y - relative.y * gradient.x);
+        }
+
+        **vorticity = curl;
+    }
+}
+
+/// Second vorticity pass: follow the gradient of `|ω|` towards regions of high
+/// rotation and apply the confinement force `ε · (N × ω_i)`, reinjecting the
+/// small-scale swirls that naive SPH dissipates.
+fn apply_vorticity_force(
+    time: Res<Time>,
+    sim_config: Res<SimConfig>,
+    spatial_hash: Res<SpatialHash>,
+    mut query: Query<(&mut Velocity, &Transform, &Vorticity, &Density)>,
+) {
+    let vorticities = query.iter().map(|(_, _, vorticity, _)| **vorticity).collect::<Vec<_>>();
+    let densities = query.iter().map(|(_, _, _, density)| **density).collect::<Vec<_>>();
+
+    for (i, (mut velocity, transform, vorticity, _)) in query.iter_mut().enumerate() {
+        let sample_point = transform.translation.xy();
+        let mut gradient_magnitude = Vec2::ZERO;
+
+        for j in spatial_hash.neighbours(sample_point) {
+            if j == i {
+                continue;
+            }
+            let offset = spatial_hash.to_neighbour(sample_point, spatial_hash.positions[j]);
+            let dst = offset.length();
+            if dst <= 0.0001 {
+                continue;
+            }
+            let gradient = smoothing_kernel_derivative(sim_config.smoothing_radius, dst) * (offset / dst);
+            gradient_magnitude += (MASS / densities[j]) * vorticities[j].abs() * gradient;
+        }
+
+        let location = gradient_magnitude / (gradient_magnitude.length() + 0.0001);
+        // `N × ω_i` in 2D: scalar curl times the perpendicular of the location vector.
+        let confinement = Vec2::new(location.y, -location.x) * **vorticity;
+        **velocity += confinement * sim_config.vorticity_epsilon * time.delta_seconds();
+    }
+}
+
 fn update_position(
     time: Res<Time>,
     mut query: Query<(&mut Transform, &Velocity, With<WaterAtom>)>,
@@ -269,19 +642,62 @@ fn update_position(
 }
 
 fn resolve_collision(
+    mut commands: Commands,
     sim_config: Res<SimConfig>,
-    mut query: Query<(&mut Transform, &mut Velocity, With<WaterAtom>)>,
+    mut query: Query<(Entity, &mut Transform, &mut Velocity), With<WaterAtom>>,
 ) {
-    let half_bounds_size = sim_config.bounds_size * 0.5 - Vec2::ONE * PARTICLE_SIZE;
+    // Reflecting walls keep a particle-sized margin; `Open`/`Periodic` act at the
+    // true domain edge so the seam stays continuous.
+    let reflect_bounds = sim_config.bounds_size * 0.5 - Vec2::ONE * PARTICLE_SIZE;
+    let half = sim_config.bounds_size * 0.5;
 
-    for (mut transform, mut velocity, _) in query.iter_mut() {
-        if transform.translation.x.abs() > half_bounds_size.x {
-            transform.translation.x = half_bounds_size.x * transform.translation.x.signum();
+    for (entity, mut transform, mut velocity) in query.iter_mut() {
+        let mut despawn = false;
+
+        // Horizontal walls.
+        if sim_config.boundary_right == Boundary::Reflect && transform.translation.x > reflect_bounds.x {
+            transform.translation.x = reflect_bounds.x;
+            velocity.x *= -1. * sim_config.collision_damping;
+        } else if sim_config.boundary_left == Boundary::Reflect && transform.translation.x < -reflect_bounds.x {
+            transform.translation.x = -reflect_bounds.x;
             velocity.x *= -1. * sim_config.collision_damping;
+        } else if transform.translation.x > half.x {
+            match sim_config.boundary_right {
+                Boundary::Open => despawn = true,
+                Boundary::Periodic => transform.translation.x -= sim_config.bounds_size.x,
+                Boundary::Reflect => {}
+            }
+        } else if transform.translation.x < -half.x {
+            match sim_config.boundary_left {
+                Boundary::Open => despawn = true,
+                Boundary::Periodic => transform.translation.x += sim_config.bounds_size.x,
+                Boundary::Reflect => {}
+            }
         }
-        if transform.translation.y.abs() > half_bounds_size.y {
-            transform.translation.y = half_bounds_size.y * transform.translation.y.signum();
+
+        // Vertical walls.
+        if sim_config.boundary_top == Boundary::Reflect && transform.translation.y > reflect_bounds.y {
+            transform.translation.y = reflect_bounds.y;
             velocity.y *= -1. * sim_config.collision_damping;
+        } else if sim_config.boundary_bottom == Boundary::Reflect && transform.translation.y < -reflect_bounds.y {
+            transform.translation.y = -reflect_bounds.y;
+            velocity.y *= -1. * sim_config.collision_damping;
+        } else if transform.translation.y > half.y {
+            match sim_config.boundary_top {
+                Boundary::Open => despawn = true,
+                Boundary::Periodic => transform.translation.y -= sim_config.bounds_size.y,
+                Boundary::Reflect => {}
+            }
+        } else if transform.translation.y < -half.y {
+            match sim_config.boundary_bottom {
+                Boundary::Open => despawn = true,
+                Boundary::Periodic => transform.translation.y += sim_config.bounds_size.y,
+                Boundary::Reflect => {}
+            }
+        }
+
+        if despawn {
+            commands.entity(entity).despawn();
         }
     }
 }
\ No newline at end of file